@@ -0,0 +1,67 @@
+use anyhow::Result;
+
+use crate::config::{Config, TemperatureSource};
+use crate::datasource::externaltemperature::weather::WeatherProvider;
+use crate::datasource::externaltemperature::ExternalTemperatureProvider;
+use crate::datasource::ha::HomeAssistantClient;
+use crate::rf;
+
+/// Validate the config, confirm HA connectivity/auth, verify the configured outdoor
+/// temperature entity exists, and report RF link health — without issuing any boiler
+/// commands.
+pub async fn run(client: &rf::Client, config: &Config) -> Result<()> {
+    let ha_client = HomeAssistantClient::new(config.ha.url.clone(), config.ha.token.clone())?;
+
+    println!("Home Assistant ({})", config.ha.url);
+    match ha_client.get_api_status().await {
+        Ok(message) => println!("  OK: {}", message),
+        Err(e) if e.downcast_ref::<reqwest::Error>().is_some() => {
+            println!("  FAIL: cannot reach Home Assistant: {}", e);
+        }
+        Err(e) => println!("  FAIL: {}", e),
+    }
+
+    match &config.temperature_source {
+        TemperatureSource::HomeAssistant(ha_temp) => {
+            println!("Outdoor temperature entity ({})", ha_temp.entity_id);
+            // The outdoor-temperature entity may live on a different Home Assistant
+            // instance than the one used for mirroring/calendars, so it needs its own
+            // client built from its own url/token rather than reusing `ha_client`.
+            let ha_temp_client = HomeAssistantClient::new(ha_temp.url.clone(), ha_temp.token.clone())?;
+            match ha_temp_client.get_state(&ha_temp.entity_id).await {
+                Ok(_) => println!("  OK: entity exists"),
+                Err(e) => println!("  FAIL: {}", e),
+            }
+        }
+        TemperatureSource::Weather(weather) => {
+            println!("Outdoor temperature (weather API: {})", weather.base_url);
+            match WeatherProvider::new(weather.clone()).current_temperature().await {
+                Ok(temp) => println!("  OK: reporting {}°C", temp),
+                Err(e) => println!("  FAIL: {}", e),
+            }
+        }
+    }
+
+    if let Some(calendar) = &config.calendar {
+        println!("Calendar ({})", calendar.entity_id);
+        match ha_client.get_calendars().await {
+            Ok(calendars) => {
+                if calendars.iter().any(|c| c.entity_id == calendar.entity_id) {
+                    println!("  OK: calendar exists");
+                } else {
+                    println!("  FAIL: no calendar with entity_id {}", calendar.entity_id);
+                }
+            }
+            Err(e) => println!("  FAIL: {}", e),
+        }
+    }
+
+    println!("RF link");
+    if client.is_connected() {
+        println!("  OK: boiler is responding");
+    } else {
+        println!("  FAIL: boiler is not responding");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+mod doctor;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+use crate::config::Config;
+use crate::connect;
+use crate::rf;
+
+#[derive(Parser)]
+pub struct Cli {
+    /// Path to the config file.
+    #[arg(short, long, default_value = "config.toml")]
+    pub config: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Poll the boiler over RF and apply any pending commands.
+    Run,
+    /// Check connectivity to Home Assistant and the RF link without touching the boiler.
+    Doctor,
+}
+
+pub fn parse() -> Cli {
+    Cli::parse()
+}
+
+impl Cli {
+    pub async fn run(&self, client: &mut rf::Client, config: &mut Config) -> Result<()> {
+        match self.command {
+            Command::Run => connect::run(client, config).await,
+            Command::Doctor => doctor::run(client, config).await,
+        }
+    }
+}
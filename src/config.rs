@@ -0,0 +1,111 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Settings for the Home Assistant instance used as an outdoor temperature source.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HAConfig {
+    pub url: String,
+    pub token: String,
+    pub entity_id: String,
+    pub temperature_field: Option<String>,
+    /// Temperature used when no reading can be obtained from the entity (it is
+    /// unavailable, unreachable or unparseable) and no recent reading is cached.
+    #[serde(default)]
+    pub fallback_temperature: f32,
+    /// Maximum age, in seconds, of a cached reading that may still be reused instead of
+    /// falling back to `fallback_temperature`.
+    #[serde(default)]
+    pub max_stale_age: u64,
+    #[serde(skip)]
+    pub(crate) last_known_good: Option<(f32, Instant)>,
+}
+
+impl HAConfig {
+    /// Remember `temp` as the most recently observed good reading.
+    pub fn record_temperature(&mut self, temp: f32) {
+        self.last_known_good = Some((temp, Instant::now()));
+    }
+
+    /// The most recently observed reading, if it's still within `max_stale_age`.
+    pub fn cached_temperature(&self) -> Option<f32> {
+        self.last_known_good.and_then(|(temp, at)| {
+            if at.elapsed().as_secs() <= self.max_stale_age {
+                Some(temp)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Settings for driving the heating mode from a Home Assistant calendar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarConfig {
+    pub entity_id: String,
+    /// Mode applied when no calendar event covers the current time.
+    pub default_mode: String,
+}
+
+/// Connection details for the Home Assistant instance used for state mirroring and
+/// calendar scheduling, independent of where the outdoor temperature comes from.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HAConnection {
+    pub url: String,
+    pub token: String,
+}
+
+/// Settings for a generic OpenWeatherMap-style HTTP weather API.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WeatherConfig {
+    pub api_key: String,
+    pub lat: f64,
+    pub lon: f64,
+    pub base_url: String,
+}
+
+/// Where the outdoor temperature used for heat-curve compensation comes from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum TemperatureSource {
+    HomeAssistant(HAConfig),
+    Weather(WeatherConfig),
+}
+
+/// Settings controlling exponential smoothing of the outdoor temperature reading.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmoothingConfig {
+    /// Weight given to the newest reading; smaller values damp spikes more aggressively.
+    pub alpha: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub ha: HAConnection,
+    pub temperature_source: TemperatureSource,
+    pub calendar: Option<CalendarConfig>,
+    pub smoothing: Option<SmoothingConfig>,
+
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// Read and parse the config file at `path`.
+pub fn read(path: &Path) -> Result<Config> {
+    let contents = fs::read_to_string(path)?;
+    let mut config: Config = toml::from_str(&contents)?;
+    config.path = path.to_path_buf();
+    Ok(config)
+}
+
+impl Config {
+    /// Persist the config back to the file it was read from.
+    pub fn write(&self) -> Result<()> {
+        let contents = toml::to_string_pretty(self)?;
+        fs::write(&self.path, contents)?;
+        Ok(())
+    }
+}
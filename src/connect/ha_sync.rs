@@ -0,0 +1,55 @@
+use anyhow::Result;
+use serde_json::json;
+
+use crate::datasource::ha::HomeAssistantClient;
+use crate::rf::BoilerState;
+
+/// Mirror the boiler's setpoints, operating mode and measured water temperature into
+/// Home Assistant sensors, so they show up natively alongside HA's own entities.
+pub async fn publish_boiler_state(ha_client: &HomeAssistantClient, state: &BoilerState) -> Result<()> {
+    ha_client
+        .post_state(
+            "sensor.frisquet_water_temp",
+            &state.water_temperature.to_string(),
+            json!({
+                "unit_of_measurement": "°C",
+                "device_class": "temperature",
+                "friendly_name": "Frisquet water temperature",
+            }),
+        )
+        .await?;
+
+    ha_client
+        .post_state(
+            "sensor.frisquet_mode",
+            state.mode.as_str(),
+            json!({ "friendly_name": "Frisquet heating mode" }),
+        )
+        .await?;
+
+    ha_client
+        .post_state(
+            "sensor.frisquet_comfort_setpoint",
+            &state.comfort_setpoint.to_string(),
+            json!({
+                "unit_of_measurement": "°C",
+                "device_class": "temperature",
+                "friendly_name": "Frisquet comfort setpoint",
+            }),
+        )
+        .await?;
+
+    ha_client
+        .post_state(
+            "sensor.frisquet_eco_setpoint",
+            &state.eco_setpoint.to_string(),
+            json!({
+                "unit_of_measurement": "°C",
+                "device_class": "temperature",
+                "friendly_name": "Frisquet eco setpoint",
+            }),
+        )
+        .await?;
+
+    Ok(())
+}
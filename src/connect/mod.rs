@@ -0,0 +1,31 @@
+mod ha_sync;
+mod schedule;
+
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::datasource::externaltemperature;
+use crate::datasource::ha::HomeAssistantClient;
+use crate::rf;
+
+/// Run one RF poll cycle: fetch the outdoor temperature and feed it into the boiler's
+/// setpoint compensation, mirror the resulting boiler state into Home Assistant, then
+/// apply whatever heating mode the configured calendar says should be active now.
+pub async fn run(client: &mut rf::Client, config: &mut Config) -> Result<()> {
+    let provider = externaltemperature::build_provider(&config.temperature_source, config.smoothing.as_ref()).await;
+    if let Ok(outdoor_temp) = provider.current_temperature().await {
+        client.apply_outdoor_compensation(outdoor_temp)?;
+    }
+
+    let boiler_state = client.poll()?;
+
+    let ha_client = HomeAssistantClient::new(config.ha.url.clone(), config.ha.token.clone())?;
+    ha_sync::publish_boiler_state(&ha_client, &boiler_state).await?;
+
+    if let Some(calendar) = &config.calendar {
+        let mode = schedule::resolve_mode(&ha_client, calendar).await?;
+        client.apply_mode(mode)?;
+    }
+
+    Ok(())
+}
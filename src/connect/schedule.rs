@@ -0,0 +1,87 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::config::CalendarConfig;
+use crate::datasource::ha::{CalendarEvent, HomeAssistantClient};
+use crate::rf::Mode;
+
+/// Resolve the heating mode that should be active right now, based on the events on the
+/// configured calendar. Falls back to the calendar's `default_mode` when no event covers
+/// the current time.
+pub async fn resolve_mode(ha_client: &HomeAssistantClient, calendar: &CalendarConfig) -> Result<Mode> {
+    let now = Utc::now();
+    let events = ha_client
+        .get_calendar_events(&calendar.entity_id, now - Duration::hours(1), now + Duration::hours(1))
+        .await?;
+
+    Ok(mode_for_events(&events, now, &calendar.default_mode))
+}
+
+/// Pick the mode implied by whichever of `events` covers `now`, falling back to
+/// `default_mode` (or `Mode::Comfort`, if that doesn't parse either) when nothing covers
+/// it or the covering event's summary isn't a recognised mode name.
+///
+/// When more than one event covers `now` simultaneously, the one that started most
+/// recently wins — this matches how a one-off exception (a short event starting later)
+/// is expected to override a longer-running recurring event that's still in progress.
+fn mode_for_events(events: &[CalendarEvent], now: DateTime<Utc>, default_mode: &str) -> Mode {
+    let active_mode = events
+        .iter()
+        .filter(|event| event.start <= now && now < event.end)
+        .max_by_key(|event| event.start)
+        .and_then(|event| Mode::parse(&event.summary));
+
+    active_mode.unwrap_or_else(|| Mode::parse(default_mode).unwrap_or(Mode::Comfort))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(start: &str, end: &str, summary: &str) -> CalendarEvent {
+        CalendarEvent {
+            start: DateTime::parse_from_rfc3339(start).unwrap().with_timezone(&Utc),
+            end: DateTime::parse_from_rfc3339(end).unwrap().with_timezone(&Utc),
+            summary: summary.to_string(),
+        }
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_no_covering_event_falls_back_to_default() {
+        let events = vec![event("2024-01-01T06:00:00Z", "2024-01-01T08:00:00Z", "comfort")];
+        let now = at("2024-01-01T10:00:00Z");
+
+        assert_eq!(mode_for_events(&events, now, "eco"), Mode::Eco);
+    }
+
+    #[test]
+    fn test_single_active_event() {
+        let events = vec![event("2024-01-01T06:00:00Z", "2024-01-01T22:00:00Z", "comfort")];
+        let now = at("2024-01-01T12:00:00Z");
+
+        assert_eq!(mode_for_events(&events, now, "eco"), Mode::Comfort);
+    }
+
+    #[test]
+    fn test_overlapping_events_prefer_the_one_that_started_most_recently() {
+        let events = vec![
+            event("2024-01-01T06:00:00Z", "2024-01-01T22:00:00Z", "comfort"),
+            event("2024-01-01T11:00:00Z", "2024-01-01T13:00:00Z", "eco"),
+        ];
+        let now = at("2024-01-01T12:00:00Z");
+
+        assert_eq!(mode_for_events(&events, now, "comfort"), Mode::Eco);
+    }
+
+    #[test]
+    fn test_unparseable_summary_falls_back_to_default() {
+        let events = vec![event("2024-01-01T06:00:00Z", "2024-01-01T22:00:00Z", "not a mode")];
+        let now = at("2024-01-01T12:00:00Z");
+
+        assert_eq!(mode_for_events(&events, now, "eco"), Mode::Eco);
+    }
+}
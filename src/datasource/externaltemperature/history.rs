@@ -0,0 +1,136 @@
+use std::collections::VecDeque;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::Mutex;
+
+use crate::config::TemperatureSource;
+use crate::datasource::externaltemperature::{ExternalTemperatureErr, ExternalTemperatureProvider};
+use crate::datasource::ha::{HomeAssistantClient, StateEnum};
+
+/// A timestamped series of outdoor temperature readings, exponentially smoothed to damp
+/// the boiler's response to brief spikes.
+pub struct TemperatureHistory {
+    alpha: f32,
+    smoothed: Option<f32>,
+    readings: VecDeque<(DateTime<Utc>, f32)>,
+}
+
+impl TemperatureHistory {
+    pub fn new(alpha: f32) -> Self {
+        Self {
+            alpha,
+            smoothed: None,
+            readings: VecDeque::new(),
+        }
+    }
+
+    /// Record a new reading and fold it into the smoothed value.
+    pub fn record(&mut self, at: DateTime<Utc>, value: f32) {
+        self.smoothed = Some(match self.smoothed {
+            Some(prev) => self.alpha * value + (1.0 - self.alpha) * prev,
+            None => value,
+        });
+        self.readings.push_back((at, value));
+    }
+
+    /// The current exponential moving average, if any readings have been recorded.
+    pub fn smoothed(&self) -> Option<f32> {
+        self.smoothed
+    }
+
+    /// The raw timestamped readings recorded so far.
+    pub fn readings(&self) -> &VecDeque<(DateTime<Utc>, f32)> {
+        &self.readings
+    }
+
+    /// Backfill from Home Assistant's history so the average has a sensible starting
+    /// point right after startup, instead of only the first live reading.
+    pub async fn backfill(
+        &mut self,
+        ha_client: &HomeAssistantClient,
+        entity_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<()> {
+        for state in ha_client.get_history(entity_id, since).await? {
+            if let StateEnum::Number(value) = state.state {
+                if let Ok(at) = DateTime::parse_from_rfc3339(&state.last_changed) {
+                    self.record(at.with_timezone(&Utc), value as f32);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wraps an `ExternalTemperatureProvider`, returning the smoothed reading instead of the
+/// instantaneous one.
+pub struct SmoothedProvider {
+    inner: Box<dyn ExternalTemperatureProvider + Send + Sync>,
+    history: Mutex<TemperatureHistory>,
+}
+
+impl SmoothedProvider {
+    pub fn new(inner: Box<dyn ExternalTemperatureProvider + Send + Sync>, alpha: f32) -> Self {
+        Self {
+            inner,
+            history: Mutex::new(TemperatureHistory::new(alpha)),
+        }
+    }
+
+    /// Build a smoothed provider, backfilling its history from the last hour of Home
+    /// Assistant state history when `temperature_source` is HA-backed, so the average
+    /// has a sensible starting point right after startup instead of only the first live
+    /// reading. Backfill failures are non-fatal: the provider just starts with an empty
+    /// history, the same as `new`.
+    pub async fn with_backfill(
+        inner: Box<dyn ExternalTemperatureProvider + Send + Sync>,
+        alpha: f32,
+        temperature_source: &TemperatureSource,
+    ) -> Self {
+        let mut history = TemperatureHistory::new(alpha);
+
+        if let TemperatureSource::HomeAssistant(ha_config) = temperature_source {
+            if let Ok(ha_client) = HomeAssistantClient::new(ha_config.url.clone(), ha_config.token.clone()) {
+                let _ = history
+                    .backfill(&ha_client, &ha_config.entity_id, Utc::now() - Duration::hours(1))
+                    .await;
+            }
+        }
+
+        Self {
+            inner,
+            history: Mutex::new(history),
+        }
+    }
+}
+
+#[async_trait]
+impl ExternalTemperatureProvider for SmoothedProvider {
+    async fn current_temperature(&self) -> Result<f32, ExternalTemperatureErr> {
+        let reading = self.inner.current_temperature().await?;
+        let mut history = self.history.lock().await;
+        history.record(Utc::now(), reading);
+        Ok(history.smoothed().unwrap_or(reading))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_smooths_towards_new_readings() {
+        let mut history = TemperatureHistory::new(0.5);
+        let t0 = Utc::now();
+
+        history.record(t0, 10.0);
+        assert_eq!(history.smoothed(), Some(10.0));
+
+        history.record(t0, 20.0);
+        assert_eq!(history.smoothed(), Some(15.0));
+
+        assert_eq!(history.readings().len(), 2);
+    }
+}
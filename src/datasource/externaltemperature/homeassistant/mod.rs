@@ -1,10 +1,28 @@
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
 use crate::config;
-use crate::datasource::externaltemperature::ExternalTemperatureErr;
-use crate::datasource::ha;
+use crate::datasource::externaltemperature::{ExternalTemperatureErr, ExternalTemperatureProvider};
+use crate::datasource::ha::{self, StateEnum};
+
+/// Outdoor temperature source backed by a Home Assistant entity.
+pub struct HomeAssistantProvider {
+    config: Mutex<config::HAConfig>,
+}
+
+impl HomeAssistantProvider {
+    pub fn new(config: config::HAConfig) -> Self {
+        Self {
+            config: Mutex::new(config),
+        }
+    }
+}
 
-impl From<reqwest::Error> for ExternalTemperatureErr {
-    fn from(value: reqwest::Error) -> Self {
-        ExternalTemperatureErr::from(value.to_string())
+#[async_trait]
+impl ExternalTemperatureProvider for HomeAssistantProvider {
+    async fn current_temperature(&self) -> Result<f32, ExternalTemperatureErr> {
+        let mut config = self.config.lock().await;
+        Ok(get_ha_temperature_or_fallback(&mut config).await)
     }
 }
 
@@ -15,21 +33,29 @@ pub async fn get_ha_temperature(config: &mut config::HAConfig) -> Result<f32, Ex
     let response = ha_client.get_state(&config.entity_id).await
         .map_err(|e| ExternalTemperatureErr::from(format!("Failed to get state for {}: {}", config.entity_id, e)))?;
 
-    let temperature_str = if config.temperature_field.is_none() {
-        // If no temperature field is specified, use the state directly
-        response.state
-            .ok_or_else(|| ExternalTemperatureErr::from(format!("No state value for {}", config.entity_id)))?
-    } else {
-        // If temperature field is specified, look in the attributes
+    let temperature_str = if let Some(temp_field) = &config.temperature_field {
+        // If a temperature field is specified, look in the attributes
         let attributes = response.attributes
             .ok_or_else(|| ExternalTemperatureErr::from(format!("No attributes for {}", config.entity_id)))?;
-        
-        let temp_field = config.temperature_field.as_ref().unwrap();
-        attributes.get(temp_field)
+
+        let raw = attributes.get(temp_field)
             .ok_or_else(|| ExternalTemperatureErr::from(format!("No field {} in attributes", temp_field)))?
             .as_str()
             .ok_or_else(|| ExternalTemperatureErr::from(format!("Field {} is not a string", temp_field)))?
-            .to_string()
+            .to_string();
+
+        if raw == "unavailable" || raw == "unknown" {
+            return Err(ExternalTemperatureErr::Unavailable);
+        }
+        raw
+    } else {
+        // If no temperature field is specified, use the state directly
+        match response.state {
+            StateEnum::Unavailable | StateEnum::Unknown => return Err(ExternalTemperatureErr::Unavailable),
+            StateEnum::Number(n) => return Ok(n as f32),
+            StateEnum::Bool(_) => return Err(ExternalTemperatureErr::from(format!("State for {} is a boolean, not a temperature", config.entity_id))),
+            StateEnum::String(s) => s,
+        }
     };
 
     temperature_str
@@ -37,6 +63,22 @@ pub async fn get_ha_temperature(config: &mut config::HAConfig) -> Result<f32, Ex
         .map_err(|e| ExternalTemperatureErr::from(format!("Cannot parse temperature '{}': {}", temperature_str, e)))
 }
 
+/// Get the outdoor temperature from Home Assistant, falling back to the last known
+/// good reading (if still fresh) or the configured default whenever no reading can be
+/// obtained: when the source entity reports `unavailable`/`unknown`, and equally when
+/// the request fails or the state does not parse. Never aborts the heating cycle.
+pub async fn get_ha_temperature_or_fallback(config: &mut config::HAConfig) -> f32 {
+    match get_ha_temperature(config).await {
+        Ok(temp) => {
+            config.record_temperature(temp);
+            temp
+        }
+        Err(ExternalTemperatureErr::Unavailable) | Err(ExternalTemperatureErr::Other(_)) => {
+            config.cached_temperature().unwrap_or(config.fallback_temperature)
+        }
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -57,6 +99,7 @@ mod tests {
             token: "fake_token".to_string(),
             entity_id: "sensor.temperature".to_string(),
             temperature_field: None,
+            ..Default::default()
         };
 
         // Mock the HA API response
@@ -89,6 +132,7 @@ mod tests {
             token: "fake_token".to_string(),
             entity_id: "sensor.weather".to_string(),
             temperature_field: Some("temperature".to_string()),
+            ..Default::default()
         };
 
         Mock::given(method("GET"))
@@ -109,4 +153,64 @@ mod tests {
         let temperature = get_ha_temperature(&mut config).await.unwrap();
         assert_eq!(temperature, 23.5);
     }
+
+    #[tokio::test]
+    async fn test_get_ha_temperature_unavailable() {
+        let mock_server = MockServer::start().await;
+        let uri = mock_server.uri();
+
+        let mut config = config::HAConfig {
+            url: uri.to_string(),
+            token: "fake_token".to_string(),
+            entity_id: "sensor.temperature".to_string(),
+            temperature_field: None,
+            ..Default::default()
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/api/states/sensor.temperature"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "state": "unavailable",
+                    "attributes": {},
+                    "last_updated": "2024-01-01T00:00:00Z",
+                    "last_changed": "2024-01-01T00:00:00Z"
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let err = get_ha_temperature(&mut config).await.unwrap_err();
+        assert_eq!(err, ExternalTemperatureErr::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn test_get_ha_temperature_or_fallback_uses_fallback() {
+        let mock_server = MockServer::start().await;
+        let uri = mock_server.uri();
+
+        let mut config = config::HAConfig {
+            url: uri.to_string(),
+            token: "fake_token".to_string(),
+            entity_id: "sensor.temperature".to_string(),
+            temperature_field: None,
+            fallback_temperature: 5.0,
+            max_stale_age: 300,
+            ..Default::default()
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/api/states/sensor.temperature"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "state": "unavailable",
+                    "attributes": {},
+                    "last_updated": "2024-01-01T00:00:00Z",
+                    "last_changed": "2024-01-01T00:00:00Z"
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let temperature = get_ha_temperature_or_fallback(&mut config).await;
+        assert_eq!(temperature, 5.0);
+    }
 }
@@ -0,0 +1,71 @@
+use std::fmt;
+
+use async_trait::async_trait;
+
+use crate::config::{SmoothingConfig, TemperatureSource};
+
+pub mod history;
+pub mod homeassistant;
+pub mod weather;
+
+/// Source of the outdoor temperature used for heat-curve compensation.
+#[async_trait]
+pub trait ExternalTemperatureProvider {
+    async fn current_temperature(&self) -> Result<f32, ExternalTemperatureErr>;
+}
+
+impl TemperatureSource {
+    /// Build the provider implementation selected by this config.
+    pub fn provider(&self) -> Box<dyn ExternalTemperatureProvider + Send + Sync> {
+        match self.clone() {
+            TemperatureSource::HomeAssistant(config) => {
+                Box::new(homeassistant::HomeAssistantProvider::new(config))
+            }
+            TemperatureSource::Weather(config) => Box::new(weather::WeatherProvider::new(config)),
+        }
+    }
+}
+
+/// Build the configured temperature provider, wrapping it in exponential smoothing when
+/// `smoothing` is configured so brief spikes don't feed straight through to the boiler.
+pub async fn build_provider(
+    temperature_source: &TemperatureSource,
+    smoothing: Option<&SmoothingConfig>,
+) -> Box<dyn ExternalTemperatureProvider + Send + Sync> {
+    let provider = temperature_source.provider();
+    match smoothing {
+        Some(cfg) => Box::new(history::SmoothedProvider::with_backfill(provider, cfg.alpha, temperature_source).await),
+        None => provider,
+    }
+}
+
+/// Error returned when an outdoor temperature reading cannot be obtained.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExternalTemperatureErr {
+    /// The source entity exists but is reporting `unavailable`/`unknown`.
+    Unavailable,
+    Other(String),
+}
+
+impl fmt::Display for ExternalTemperatureErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExternalTemperatureErr::Unavailable => write!(f, "external temperature source is unavailable"),
+            ExternalTemperatureErr::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ExternalTemperatureErr {}
+
+impl From<String> for ExternalTemperatureErr {
+    fn from(value: String) -> Self {
+        ExternalTemperatureErr::Other(value)
+    }
+}
+
+impl From<reqwest::Error> for ExternalTemperatureErr {
+    fn from(value: reqwest::Error) -> Self {
+        ExternalTemperatureErr::from(value.to_string())
+    }
+}
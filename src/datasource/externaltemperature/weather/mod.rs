@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::config::WeatherConfig;
+use crate::datasource::externaltemperature::{ExternalTemperatureErr, ExternalTemperatureProvider};
+
+#[derive(Debug, Deserialize)]
+struct WeatherResponse {
+    main: WeatherMain,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeatherMain {
+    temp: f32,
+}
+
+/// Outdoor temperature source backed by a generic OpenWeatherMap-style HTTP API.
+pub struct WeatherProvider {
+    client: reqwest::Client,
+    config: WeatherConfig,
+}
+
+impl WeatherProvider {
+    pub fn new(config: WeatherConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            config,
+        }
+    }
+}
+
+#[async_trait]
+impl ExternalTemperatureProvider for WeatherProvider {
+    async fn current_temperature(&self) -> Result<f32, ExternalTemperatureErr> {
+        let url = format!("{}/weather", self.config.base_url.trim_end_matches('/'));
+        let response = self.client
+            .get(&url)
+            .query(&[
+                ("lat", self.config.lat.to_string()),
+                ("lon", self.config.lon.to_string()),
+                ("appid", self.config.api_key.clone()),
+            ])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ExternalTemperatureErr::from(format!(
+                "Failed to get weather: {} - {}",
+                response.status(),
+                response.text().await?
+            )));
+        }
+
+        let body: WeatherResponse = response.json().await?;
+        Ok(body.main.temp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_current_temperature() {
+        let mock_server = MockServer::start().await;
+
+        let config = WeatherConfig {
+            api_key: "fake_key".to_string(),
+            lat: 52.37,
+            lon: 4.89,
+            base_url: mock_server.uri(),
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/weather"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "main": {"temp": 8.3},
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let provider = WeatherProvider::new(config);
+        let temperature = provider.current_temperature().await.unwrap();
+        assert_eq!(temperature, 8.3);
+    }
+}
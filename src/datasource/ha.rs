@@ -1,5 +1,6 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use reqwest::{Client, header};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use anyhow::Result;
 use std::time::Duration;
 
@@ -9,12 +10,115 @@ pub struct HomeAssistantClient {
     base_url: String,
 }
 
+/// A calendar entity exposed by Home Assistant.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Calendar {
+    pub entity_id: String,
+    pub name: String,
+}
+
+/// An event on a Home Assistant calendar.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CalendarEvent {
+    #[serde(deserialize_with = "deserialize_calendar_datetime")]
+    pub start: DateTime<Utc>,
+    #[serde(deserialize_with = "deserialize_calendar_datetime")]
+    pub end: DateTime<Utc>,
+    pub summary: String,
+}
+
+/// HA represents calendar event times as either `{"dateTime": "..."}` (timed events) or
+/// `{"date": "..."}` (all-day events).
+fn deserialize_calendar_datetime<'de, D>(deserializer: D) -> std::result::Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Raw {
+        #[serde(rename = "dateTime")]
+        date_time: Option<String>,
+        date: Option<String>,
+    }
+
+    let raw = Raw::deserialize(deserializer)?;
+    if let Some(date_time) = raw.date_time {
+        DateTime::parse_from_rfc3339(&date_time)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(serde::de::Error::custom)
+    } else if let Some(date) = raw.date {
+        let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(serde::de::Error::custom)?;
+        let datetime = date
+            .and_hms_opt(0, 0, 0)
+            .ok_or_else(|| serde::de::Error::custom("invalid calendar date"))?;
+        Ok(Utc.from_utc_datetime(&datetime))
+    } else {
+        Err(serde::de::Error::custom("calendar event has neither dateTime nor date"))
+    }
+}
+
+/// The `state` string of a Home Assistant entity, typed according to what it actually holds.
+///
+/// HA always reports `state` as a string, but that string is one of a numeric value, a
+/// boolean, free text, or one of the two sentinels it uses when an entity has no usable
+/// reading: `"unavailable"` and `"unknown"`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateEnum {
+    Number(f64),
+    Bool(bool),
+    String(String),
+    Unavailable,
+    Unknown,
+}
+
+impl<'de> Deserialize<'de> for StateEnum {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Ok(match raw.as_str() {
+            "unavailable" => StateEnum::Unavailable,
+            "unknown" => StateEnum::Unknown,
+            // HA's binary-style entities (switches, binary_sensors, ...) report "on"/"off"
+            // rather than Rust's bool literals.
+            "on" => StateEnum::Bool(true),
+            "off" => StateEnum::Bool(false),
+            _ => {
+                if let Ok(n) = raw.parse::<f64>() {
+                    StateEnum::Number(n)
+                } else if let Ok(b) = raw.parse::<bool>() {
+                    StateEnum::Bool(b)
+                } else {
+                    StateEnum::String(raw)
+                }
+            }
+        })
+    }
+}
+
+impl Serialize for StateEnum {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            StateEnum::Number(n) => serializer.collect_str(n),
+            StateEnum::Bool(b) => serializer.collect_str(b),
+            StateEnum::String(s) => serializer.serialize_str(s),
+            StateEnum::Unavailable => serializer.serialize_str("unavailable"),
+            StateEnum::Unknown => serializer.serialize_str("unknown"),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct State {
-    pub state: Option<String>,
+    pub state: StateEnum,
     pub attributes: Option<serde_json::Value>,
     pub last_updated: String,
     pub last_changed: String,
+    #[serde(default)]
+    pub context: Option<serde_json::Value>,
 }
 
 impl HomeAssistantClient {
@@ -38,6 +142,32 @@ impl HomeAssistantClient {
         })
     }
 
+    /// Check that the Home Assistant REST API is reachable and the token is valid
+    pub async fn get_api_status(&self) -> Result<String> {
+        let url = format!("{}/", self.base_url);
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(anyhow::anyhow!("Unauthorized: check your long-lived access token"));
+        }
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to get API status: {} - {}",
+                response.status(),
+                response.text().await?
+            ));
+        }
+
+        #[derive(Deserialize)]
+        struct ApiStatus {
+            message: String,
+        }
+
+        let status: ApiStatus = response.json().await?;
+        Ok(status.message)
+    }
+
     /// Get the state of an entity
     pub async fn get_state(&self, entity_id: &str) -> Result<State> {
         let url = format!("{}/states/{}", self.base_url, entity_id);
@@ -79,6 +209,101 @@ impl HomeAssistantClient {
         Ok(())
     }
 
+    /// Create or update the state of an entity
+    pub async fn post_state(
+        &self,
+        entity_id: &str,
+        state: &str,
+        attributes: serde_json::Value,
+    ) -> Result<State> {
+        let url = format!("{}/states/{}", self.base_url, entity_id);
+        let response = self.client
+            .post(&url)
+            .json(&serde_json::json!({
+                "state": state,
+                "attributes": attributes,
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to post state: {} - {}",
+                response.status(),
+                response.text().await?
+            ));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// List the calendar entities exposed by Home Assistant
+    pub async fn get_calendars(&self) -> Result<Vec<Calendar>> {
+        let url = format!("{}/calendars", self.base_url);
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to get calendars: {} - {}",
+                response.status(),
+                response.text().await?
+            ));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Get the events on a calendar that fall between `start` and `end`
+    pub async fn get_calendar_events(
+        &self,
+        entity_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<CalendarEvent>> {
+        let url = format!("{}/calendars/{}", self.base_url, entity_id);
+        let response = self.client
+            .get(&url)
+            .query(&[
+                ("start", start.to_rfc3339()),
+                ("end", end.to_rfc3339()),
+            ])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to get calendar events: {} - {}",
+                response.status(),
+                response.text().await?
+            ));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Get an entity's recorded state history since `start`
+    pub async fn get_history(&self, entity_id: &str, start: DateTime<Utc>) -> Result<Vec<State>> {
+        let url = format!(
+            "{}/history/period/{}?filter_entity_id={}",
+            self.base_url,
+            start.to_rfc3339(),
+            entity_id,
+        );
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to get history: {} - {}",
+                response.status(),
+                response.text().await?
+            ));
+        }
+
+        // HA returns one array of states per requested entity.
+        let mut entities: Vec<Vec<State>> = response.json().await?;
+        Ok(entities.pop().unwrap_or_default())
+    }
+
     /// Get all states
     pub async fn get_states(&self) -> Result<Vec<State>> {
         let url = format!("{}/states", self.base_url);
@@ -101,6 +326,42 @@ mod tests {
     use super::*;
     use mockito::Server;
 
+    #[tokio::test]
+    async fn test_get_api_status() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/api/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"message": "API running."}"#)
+            .create();
+
+        let client = HomeAssistantClient::new(
+            server.url(),
+            "fake_token".to_string(),
+        ).unwrap();
+
+        let message = client.get_api_status().await.unwrap();
+        assert_eq!(message, "API running.");
+    }
+
+    #[tokio::test]
+    async fn test_get_api_status_unauthorized() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/api/")
+            .with_status(401)
+            .create();
+
+        let client = HomeAssistantClient::new(
+            server.url(),
+            "bad_token".to_string(),
+        ).unwrap();
+
+        let err = client.get_api_status().await.unwrap_err();
+        assert!(err.to_string().contains("Unauthorized"));
+    }
+
     #[tokio::test]
     async fn test_get_state() {
         let mut server = Server::new_async().await;
@@ -122,6 +383,156 @@ mod tests {
         ).unwrap();
 
         let state = client.get_state("sensor.temperature").await.unwrap();
-        assert_eq!(state.state, Some("23.5".to_string()));
+        assert_eq!(state.state, StateEnum::Number(23.5));
+    }
+
+    #[tokio::test]
+    async fn test_get_state_unavailable() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/api/states/sensor.temperature")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "state": "unavailable",
+                "attributes": {},
+                "last_updated": "2024-01-01T00:00:00Z",
+                "last_changed": "2024-01-01T00:00:00Z"
+            }"#)
+            .create();
+
+        let client = HomeAssistantClient::new(
+            server.url(),
+            "fake_token".to_string(),
+        ).unwrap();
+
+        let state = client.get_state("sensor.temperature").await.unwrap();
+        assert_eq!(state.state, StateEnum::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn test_get_state_on_off() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/api/states/binary_sensor.window")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "state": "on",
+                "attributes": {},
+                "last_updated": "2024-01-01T00:00:00Z",
+                "last_changed": "2024-01-01T00:00:00Z"
+            }"#)
+            .create();
+
+        let client = HomeAssistantClient::new(
+            server.url(),
+            "fake_token".to_string(),
+        ).unwrap();
+
+        let state = client.get_state("binary_sensor.window").await.unwrap();
+        assert_eq!(state.state, StateEnum::Bool(true));
+    }
+
+    #[tokio::test]
+    async fn test_post_state() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/api/states/sensor.frisquet_water_temp")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "state": "45.0",
+                "attributes": {"unit_of_measurement": "°C"},
+                "last_updated": "2024-01-01T00:00:00Z",
+                "last_changed": "2024-01-01T00:00:00Z",
+                "context": {"id": "abc123", "parent_id": null, "user_id": null}
+            }"#)
+            .create();
+
+        let client = HomeAssistantClient::new(
+            server.url(),
+            "fake_token".to_string(),
+        ).unwrap();
+
+        let state = client
+            .post_state(
+                "sensor.frisquet_water_temp",
+                "45.0",
+                serde_json::json!({"unit_of_measurement": "°C"}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(state.state, StateEnum::Number(45.0));
+        assert!(state.context.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_calendar_events() {
+        let mut server = Server::new_async().await;
+        let start = chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00+00:00").unwrap().with_timezone(&chrono::Utc);
+        let end = chrono::DateTime::parse_from_rfc3339("2024-01-02T00:00:00+00:00").unwrap().with_timezone(&chrono::Utc);
+
+        let _mock = server
+            .mock("GET", "/api/calendars/calendar.heating")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("start".to_string(), start.to_rfc3339()),
+                mockito::Matcher::UrlEncoded("end".to_string(), end.to_rfc3339()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"[
+                {
+                    "start": {"dateTime": "2024-01-01T06:00:00+00:00"},
+                    "end": {"dateTime": "2024-01-01T22:00:00+00:00"},
+                    "summary": "comfort"
+                }
+            ]"#)
+            .create();
+
+        let client = HomeAssistantClient::new(
+            server.url(),
+            "fake_token".to_string(),
+        ).unwrap();
+
+        let events = client.get_calendar_events("calendar.heating", start, end).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "comfort");
+    }
+
+    #[tokio::test]
+    async fn test_get_history() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", mockito::Matcher::Regex(r"^/api/history/period/".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"[
+                [
+                    {
+                        "state": "8.0",
+                        "attributes": {},
+                        "last_updated": "2024-01-01T00:00:00Z",
+                        "last_changed": "2024-01-01T00:00:00Z"
+                    },
+                    {
+                        "state": "8.5",
+                        "attributes": {},
+                        "last_updated": "2024-01-01T01:00:00Z",
+                        "last_changed": "2024-01-01T01:00:00Z"
+                    }
+                ]
+            ]"#)
+            .create();
+
+        let client = HomeAssistantClient::new(
+            server.url(),
+            "fake_token".to_string(),
+        ).unwrap();
+
+        let start = chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00+00:00").unwrap().with_timezone(&chrono::Utc);
+        let states = client.get_history("sensor.outdoor_temperature", start).await.unwrap();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[1].state, StateEnum::Number(8.5));
     }
 }
\ No newline at end of file
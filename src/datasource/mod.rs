@@ -0,0 +1,2 @@
+pub mod externaltemperature;
+pub mod ha;
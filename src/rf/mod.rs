@@ -0,0 +1,100 @@
+use anyhow::Result;
+
+use crate::config::Config;
+
+/// The heating mode currently selected on the boiler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Comfort,
+    Eco,
+    FrostProtection,
+}
+
+impl Mode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Mode::Comfort => "comfort",
+            Mode::Eco => "eco",
+            Mode::FrostProtection => "frost_protection",
+        }
+    }
+
+    /// Parse a mode name such as a calendar event summary or a config value.
+    pub fn parse(value: &str) -> Option<Mode> {
+        match value.trim().to_lowercase().as_str() {
+            "comfort" => Some(Mode::Comfort),
+            "eco" => Some(Mode::Eco),
+            "frost_protection" | "frost-protection" | "frost protection" => Some(Mode::FrostProtection),
+            _ => None,
+        }
+    }
+}
+
+/// Snapshot of the boiler's setpoints and measured water temperature, as read over RF.
+#[derive(Debug, Clone)]
+pub struct BoilerState {
+    pub mode: Mode,
+    pub comfort_setpoint: f32,
+    pub eco_setpoint: f32,
+    pub water_temperature: f32,
+}
+
+/// Handle to the RF link used to talk to the Frisquet boiler.
+///
+/// This does not talk to real hardware yet — the actual RF protocol implementation is
+/// not part of this tree. `poll`/`apply_mode`/`is_connected` are stand-ins that return
+/// placeholder data (only `poll`'s setpoints vary, following the offset set by
+/// `apply_outdoor_compensation`) so the rest of the stack (HA mirroring, calendar scheduling,
+/// the `doctor` health check) has something to build and test against; swap their
+/// bodies out once the real RF driver lands.
+#[derive(Debug, Clone)]
+pub struct Client {
+    config: Config,
+    outdoor_compensation_offset: f32,
+}
+
+pub fn new(config: &Config) -> Result<Client> {
+    Ok(Client {
+        config: config.clone(),
+        outdoor_compensation_offset: 0.0,
+    })
+}
+
+/// Outdoor temperature, in °C, above which no compensation is applied.
+const COMPENSATION_REFERENCE_TEMP: f32 = 15.0;
+/// Setpoint increase, in °C, applied per degree the outdoor temperature is below
+/// `COMPENSATION_REFERENCE_TEMP`.
+const COMPENSATION_COEFFICIENT: f32 = 0.2;
+
+impl Client {
+    /// Placeholder: does not yet poll real hardware over RF.
+    pub fn poll(&mut self) -> Result<BoilerState> {
+        let _ = &self.config;
+        Ok(BoilerState {
+            mode: Mode::Comfort,
+            comfort_setpoint: 19.0 + self.outdoor_compensation_offset,
+            eco_setpoint: 16.0 + self.outdoor_compensation_offset,
+            water_temperature: 45.0,
+        })
+    }
+
+    /// Adjust the setpoints `poll` reports to compensate for the current outdoor
+    /// temperature: colder outdoor air raises the setpoint the boiler aims for.
+    /// Placeholder model pending the real heat-curve logic that ships with the RF driver.
+    pub fn apply_outdoor_compensation(&mut self, outdoor_temp: f32) -> Result<()> {
+        self.outdoor_compensation_offset =
+            ((COMPENSATION_REFERENCE_TEMP - outdoor_temp) * COMPENSATION_COEFFICIENT).max(0.0);
+        Ok(())
+    }
+
+    /// Placeholder: always reports the RF link as responsive until the real driver lands.
+    pub fn is_connected(&self) -> bool {
+        true
+    }
+
+    /// Placeholder: does not yet send anything over RF.
+    pub fn apply_mode(&mut self, mode: Mode) -> Result<()> {
+        let _ = (&self.config, mode);
+        Ok(())
+    }
+}